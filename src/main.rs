@@ -4,17 +4,174 @@ use std::{
 };
 
 use anyhow::{Context, Result};
+use chrono::NaiveDate;
 use clap::Parser;
 use fs_extra::dir::{self, CopyOptions};
 use handlebars::Handlebars;
-use pulldown_cmark::{html::push_html, CowStr, Event, Options, Tag};
+use notify::{RecursiveMode, Watcher};
+use pulldown_cmark::{html::push_html, CowStr, Event, Options, Tag, TagEnd};
 use serde_json::Value;
 use shellexpand::tilde;
+use syntect::highlighting::{Theme, ThemeSet};
+use syntect::html::{css_for_theme_with_class_style, ClassStyle, ClassedHTMLGenerator};
+use syntect::parsing::SyntaxSet;
+use syntect::util::LinesWithEndings;
 use walkdir::WalkDir;
 
 fn main() {
     let args = Args::parse();
-    generate_site(args)
+    if args.serve || args.watch {
+        run_dev_mode(args);
+    } else {
+        generate_site(&args);
+    }
+}
+
+/// Runs the interactive authoring workflow: build the site once, then (optionally) serve it
+/// and watch the content, header, footer, and stylesheet for changes, rebuilding as they occur.
+///
+/// # Arguments
+/// * `args` - Parsed command line arguments.
+fn run_dev_mode(args: Args) {
+    let output_path = generate_site(&args);
+
+    if args.serve {
+        let addr = args.serve_addr.clone();
+        let serve_root = output_path.clone();
+        std::thread::spawn(move || serve_output(&serve_root, &addr));
+    }
+
+    watch_and_rebuild(&args, &output_path);
+}
+
+/// Serves `root` over HTTP at `addr`, mapping `/` and directory-style paths to `index.html`.
+///
+/// # Arguments
+/// * `root` - The site's output directory to serve files from.
+/// * `addr` - The address to bind to, e.g. `127.0.0.1:8080`.
+fn serve_output(root: &Path, addr: &str) {
+    let server = match tiny_http::Server::http(addr) {
+        Ok(server) => server,
+        Err(e) => {
+            eprintln!("Error starting development server on {}: {}", addr, e);
+            return;
+        }
+    };
+    println!("Serving {:?} at http://{}", root, addr);
+
+    for request in server.incoming_requests() {
+        let mut requested = request.url().trim_start_matches('/').to_string();
+        if requested.is_empty() || requested.ends_with('/') {
+            requested.push_str("index.html");
+        }
+        // Collapse `..`/`.` components so a crafted URL can't escape `root`.
+        let requested = normalize_path(Path::new(&requested));
+
+        let response = match fs::read(root.join(&requested)) {
+            Ok(contents) => tiny_http::Response::from_data(contents),
+            Err(_) => tiny_http::Response::from_string("404 Not Found").with_status_code(404),
+        };
+
+        if let Err(e) = request.respond(response) {
+            eprintln!("Error responding to request: {}", e);
+        }
+    }
+}
+
+/// Watches the content directory, header, footer, and stylesheet for changes, debouncing
+/// bursts of filesystem events and triggering either a targeted `build_one` rebuild (for a
+/// single changed markdown file) or a full `generate_site` rebuild (for header, footer, or
+/// style changes, since those are reflected on every page).
+///
+/// # Arguments
+/// * `args` - Parsed command line arguments.
+/// * `output_path` - The site's output directory, from the most recent full build.
+fn watch_and_rebuild(args: &Args, output_path: &Path) {
+    let content_path = get_content_path(&args.content)
+        .map_err(|e| eprintln!("Error extracting the content path: {}", e))
+        .unwrap_or_else(|_| std::process::exit(1));
+    let header_path = get_absolute_path(&args.header).ok();
+    let footer_path = get_absolute_path(&args.footer).ok();
+    let style_path = get_absolute_path(&args.style).ok();
+
+    let (tx, rx) = std::sync::mpsc::channel();
+    let mut watcher = notify::recommended_watcher(tx)
+        .map_err(|e| eprintln!("Error starting file watcher: {}", e))
+        .unwrap_or_else(|_| std::process::exit(1));
+
+    watcher
+        .watch(&content_path, RecursiveMode::Recursive)
+        .map_err(|e| eprintln!("Error watching content directory: {}", e))
+        .unwrap_or_else(|_| std::process::exit(1));
+    for watched in [&header_path, &footer_path, &style_path].into_iter().flatten() {
+        let _ = watcher.watch(watched, RecursiveMode::NonRecursive);
+    }
+
+    println!("Watching {:?} for changes...", content_path);
+
+    let debounce = std::time::Duration::from_millis(200);
+    while let Ok(first_event) = rx.recv() {
+        let mut changed_paths: Vec<PathBuf> = Vec::new();
+        if let Ok(event) = first_event {
+            changed_paths.extend(event.paths);
+        }
+        // Drain any further events arriving within the debounce window so a burst of
+        // filesystem notifications (e.g. an editor's save-then-rename) triggers one rebuild.
+        while let Ok(Ok(event)) = rx.recv_timeout(debounce) {
+            changed_paths.extend(event.paths);
+        }
+
+        let touches_global = changed_paths.iter().any(|path| {
+            Some(path.as_path()) == header_path.as_deref()
+                || Some(path.as_path()) == footer_path.as_deref()
+                || Some(path.as_path()) == style_path.as_deref()
+        });
+
+        if touches_global {
+            println!("Header, footer, or stylesheet changed; rebuilding the whole site.");
+            generate_site(args);
+            continue;
+        }
+
+        for path in &changed_paths {
+            if path.starts_with(&content_path) && path.extension().map_or(false, |e| e == "md") {
+                rebuild_one_file(path, args, &content_path, output_path);
+            }
+        }
+    }
+}
+
+/// Re-renders a single changed markdown file without rebuilding the rest of the site.
+///
+/// The site-wide aggregates (blog index, search index, broken-link check) are refreshed on
+/// the next full rebuild rather than after every keystroke.
+///
+/// # Arguments
+/// * `path` - Path to the changed markdown file.
+/// * `args` - Parsed command line arguments.
+/// * `content_path` - Path to the content directory.
+/// * `output_path` - Path to the site's output directory.
+fn rebuild_one_file(path: &Path, args: &Args, content_path: &Path, output_path: &Path) {
+    let syntax_set = SyntaxSet::load_defaults_newlines();
+    let theme = load_syntax_theme(&args.syntax_theme);
+    let style = build_style(&args.style, theme.as_ref());
+    let header = load_header(&args.header, content_path, args.toc_levels, &syntax_set, theme.as_ref());
+    let footer = load_footer(&args.footer, content_path, args.toc_levels, &syntax_set, theme.as_ref());
+
+    match build_one(
+        path,
+        content_path,
+        output_path,
+        style.as_deref(),
+        header.as_ref(),
+        footer.as_ref(),
+        args.toc_levels,
+        &syntax_set,
+        theme.as_ref(),
+    ) {
+        Ok(_) => println!("Rebuilt {:?}", path),
+        Err(e) => eprintln!("Error rebuilding {:?}: {}", path, e),
+    }
 }
 
 /// Command line arguments for the application.
@@ -39,17 +196,45 @@ struct Args {
     /// Site output directory. Will be created if it doesn't already exist.
     #[arg(short, long, default_value = "_site")]
     output: String,
+
+    /// Maximum heading depth (1 = only `#`, 2 = `#` and `##`, ...) included in the table of contents.
+    #[arg(long, default_value_t = 3)]
+    toc_levels: usize,
+
+    /// Don't fail the build when internal links point at files that were never emitted.
+    #[arg(long)]
+    allow_broken_links: bool,
+
+    /// Watch content, header, footer, and the stylesheet, rebuilding automatically on change.
+    /// Implied by `--serve`.
+    #[arg(long)]
+    watch: bool,
+
+    /// Serve the generated site locally and rebuild it on changes (implies `--watch`).
+    #[arg(long)]
+    serve: bool,
+
+    /// Address the development server listens on.
+    #[arg(long, default_value = "127.0.0.1:8080")]
+    serve_addr: String,
+
+    /// Name of the bundled syntect theme used to syntax-highlight fenced code blocks.
+    #[arg(long, default_value = "InspiredGitHub")]
+    syntax_theme: String,
 }
 
 /// Generates the static site using provided command line arguments.
 ///
 /// # Arguments
 /// * `args` - Parsed command line arguments.
-fn generate_site(args: Args) {
-    let content_path = get_content_path(args.content)
+///
+/// # Returns
+/// * The absolute path the site was written to, for callers (e.g. dev-server mode) that need it afterward.
+fn generate_site(args: &Args) -> PathBuf {
+    let content_path = get_content_path(&args.content)
         .map_err(|e| eprintln!("Error extracting the content path: {}", e))
         .unwrap_or_else(|_| std::process::exit(1));
-    let output_path = get_output_path(args.output)
+    let output_path = get_output_path(&args.output)
         .map_err(|e| eprintln!("Error preparing the output path: {}", e))
         .unwrap_or_else(|_| std::process::exit(1));
 
@@ -57,9 +242,20 @@ fn generate_site(args: Args) {
         .map_err(|e| eprintln!("Error copying static assets: {}", e))
         .unwrap_or_else(|_| std::process::exit(1));
 
-    let style = load_style(&args.style);
-    let header = load_header(&args.header, &content_path);
-    let footer = load_footer(&args.footer, &content_path);
+    let syntax_set = SyntaxSet::load_defaults_newlines();
+    let theme = load_syntax_theme(&args.syntax_theme);
+    let style = build_style(&args.style, theme.as_ref());
+    let header = load_header(&args.header, &content_path, args.toc_levels, &syntax_set, theme.as_ref());
+    let footer = load_footer(&args.footer, &content_path, args.toc_levels, &syntax_set, theme.as_ref());
+
+    let mut posts: Vec<PostSummary> = Vec::new();
+    let mut search_index: serde_json::Map<String, Value> = serde_json::Map::new();
+    let mut link_checks: Vec<(PathBuf, String)> = Vec::new();
+    // Lowercased tag text -> (display name as first seen, pages carrying that tag). Keyed by the
+    // tag's own text rather than its slug so that distinct tags whose slugs collide (e.g. "C++"
+    // and "C#") are still grouped separately.
+    let mut tag_pages: std::collections::HashMap<String, (String, Vec<TaggedPage>)> =
+        std::collections::HashMap::new();
 
     for entry in WalkDir::new(&content_path)
         .into_iter()
@@ -67,36 +263,464 @@ fn generate_site(args: Args) {
         .filter_map(|e| e.ok())
     {
         if entry.file_type().is_file() && entry.path().extension().map_or(false, |e| e == "md") {
-            let html_content = load_html_from_md_file(entry.path(), &content_path)
-                .map_err(|e| eprintln!("Error rendering markdown to HTML: {}", e))
-                .unwrap_or_else(|_| std::process::exit(1));
+            let info = build_one(
+                entry.path(),
+                &content_path,
+                &output_path,
+                style.as_deref(),
+                header.as_ref(),
+                footer.as_ref(),
+                args.toc_levels,
+                &syntax_set,
+                theme.as_ref(),
+            )
+            .map_err(|e| eprintln!("Error building {:?}: {}", entry.path(), e))
+            .unwrap_or_else(|_| std::process::exit(1));
+
+            if let Some(post) = info.post {
+                posts.push(post);
+            }
+            for link in info.internal_links {
+                link_checks.push((info.relative_path.clone(), link));
+            }
+            if let Some(tagged_page) = info.tagged_page {
+                for tag in &info.tags {
+                    let (_, pages) = tag_pages
+                        .entry(tag.trim().to_lowercase())
+                        .or_insert_with(|| (tag.clone(), Vec::new()));
+                    pages.push(TaggedPage {
+                        title: tagged_page.title.clone(),
+                        url: tagged_page.url.clone(),
+                    });
+                }
+            }
+            search_index.insert(info.search_id, info.search_entry);
+        }
+    }
 
-            let relative_path = entry
-                .path()
-                .strip_prefix(&content_path)
-                .unwrap()
-                .with_extension("html");
-            let output_path = output_path.join(&relative_path);
+    let search_index_json = serde_json::to_string(&search_index)
+        .map_err(|e| eprintln!("Error serializing search index: {}", e))
+        .unwrap_or_else(|_| std::process::exit(1));
+    fs::write(output_path.join("search_index.json"), search_index_json)
+        .map_err(|e| eprintln!("Error writing search index to file: {}", e))
+        .unwrap_or_else(|_| std::process::exit(1));
 
-            let final_html = render_template(
+    if !posts.is_empty() {
+        posts.sort_by(|a, b| b.date.cmp(&a.date));
+
+        let posts_json: Vec<Value> = posts
+            .iter()
+            .map(|post| {
+                serde_json::json!({
+                    "title": post.title,
+                    "date": post.date.format("%Y-%m-%d").to_string(),
+                    "url": post.url,
+                })
+            })
+            .collect();
+
+        let index_html = render_template(
+            style.as_deref(),
+            header.as_ref().map(|content| content.html.as_str()),
+            footer.as_ref().map(|content| content.html.as_str()),
+            HTMLContent {
+                front_matter: None,
+                html: String::new(),
+                plain_text: String::new(),
+                headings: Vec::new(),
+                toc: Vec::new(),
+                internal_links: Vec::new(),
+            },
+            Some(serde_json::json!({ "posts": posts_json })),
+        )
+        .map_err(|e| eprintln!("Error rendering post index: {}", e))
+        .unwrap_or_else(|_| std::process::exit(1));
+
+        fs::write(output_path.join("posts.html"), index_html)
+            .map_err(|e| eprintln!("Error writing post index to file: {}", e))
+            .unwrap_or_else(|_| std::process::exit(1));
+    }
+
+    if !tag_pages.is_empty() {
+        let tags_dir = output_path.join("tags");
+        fs::create_dir_all(&tags_dir)
+            .map_err(|e| eprintln!("Error creating tags directory: {}", e))
+            .unwrap_or_else(|_| std::process::exit(1));
+
+        let mut sorted_tags: Vec<(String, Vec<TaggedPage>)> = tag_pages.into_values().collect();
+        sorted_tags.sort_by(|(a, _), (b, _)| a.cmp(b));
+
+        let mut tags_index: Vec<Value> = Vec::new();
+        // Seed with "index" so a tag that slugifies to it (e.g. "Index") can't collide with
+        // the tag-index page written below at `tags/index.html`.
+        let mut seen_slugs: std::collections::HashMap<String, usize> =
+            std::collections::HashMap::from([("index".to_string(), 1)]);
+        for (tag, mut pages) in sorted_tags {
+            let slug = unique_slug(&slugify(&tag), &mut seen_slugs);
+            pages.sort_by(|a, b| a.title.cmp(&b.title));
+
+            let pages_json: Vec<Value> = pages
+                .iter()
+                .map(|page| serde_json::json!({ "title": page.title, "url": page.url }))
+                .collect();
+
+            let tag_html = render_template(
                 style.as_deref(),
                 header.as_ref().map(|content| content.html.as_str()),
                 footer.as_ref().map(|content| content.html.as_str()),
-                html_content,
+                HTMLContent {
+                    front_matter: None,
+                    html: String::new(),
+                    plain_text: String::new(),
+                    headings: Vec::new(),
+                    toc: Vec::new(),
+                    internal_links: Vec::new(),
+                },
+                Some(serde_json::json!({ "tag": tag, "pages": pages_json })),
             )
-            .map_err(|e| eprintln!("Error rendering template: {}", e))
+            .map_err(|e| eprintln!("Error rendering tag page {:?}: {}", tag, e))
             .unwrap_or_else(|_| std::process::exit(1));
 
-            if let Some(parent) = output_path.parent() {
-                fs::create_dir_all(parent)
-                    .map_err(|e| eprintln!("Error creating content directory: {}", e))
-                    .unwrap_or_else(|_| std::process::exit(1));
-            }
-            fs::write(output_path, final_html)
-                .map_err(|e| eprintln!("Error writing generated HTML to file: {}", e))
+            fs::write(tags_dir.join(format!("{}.html", slug)), tag_html)
+                .map_err(|e| eprintln!("Error writing tag page {:?}: {}", slug, e))
                 .unwrap_or_else(|_| std::process::exit(1));
+
+            tags_index.push(serde_json::json!({ "tag": tag, "slug": slug, "count": pages.len() }));
+        }
+
+        let tags_index_html = render_template(
+            style.as_deref(),
+            header.as_ref().map(|content| content.html.as_str()),
+            footer.as_ref().map(|content| content.html.as_str()),
+            HTMLContent {
+                front_matter: None,
+                html: String::new(),
+                plain_text: String::new(),
+                headings: Vec::new(),
+                toc: Vec::new(),
+                internal_links: Vec::new(),
+            },
+            Some(serde_json::json!({ "tags": tags_index })),
+        )
+        .map_err(|e| eprintln!("Error rendering tag index: {}", e))
+        .unwrap_or_else(|_| std::process::exit(1));
+
+        fs::write(tags_dir.join("index.html"), tags_index_html)
+            .map_err(|e| eprintln!("Error writing tag index to file: {}", e))
+            .unwrap_or_else(|_| std::process::exit(1));
+    }
+
+    check_internal_links(&link_checks, &output_path, args.allow_broken_links);
+
+    output_path
+}
+
+/// Loads a bundled syntect theme by name for fenced code block highlighting.
+///
+/// # Arguments
+/// * `name` - The theme's name, as listed in syntect's default theme set (e.g. `InspiredGitHub`).
+///
+/// # Returns
+/// * `Some(Theme)` if the name matches a bundled theme, `None` (with a warning) otherwise, which
+///   disables syntax highlighting for the build.
+fn load_syntax_theme(name: &str) -> Option<Theme> {
+    let theme_set = ThemeSet::load_defaults();
+    theme_set.themes.get(name).cloned().or_else(|| {
+        eprintln!(
+            "Warning: unknown syntax theme {:?}; syntax highlighting is disabled.",
+            name
+        );
+        None
+    })
+}
+
+/// Builds the final stylesheet passed to `render_template`, inlining the syntax theme's
+/// generated CSS ahead of the user's stylesheet so page styles can still override it.
+///
+/// # Arguments
+/// * `style_path` - Path to the user's stylesheet, as given on the command line.
+/// * `theme` - The loaded syntax theme, if any.
+///
+/// # Returns
+/// * The combined stylesheet content, or `None` if neither a theme nor a user stylesheet is available.
+fn build_style(style_path: &str, theme: Option<&Theme>) -> Option<String> {
+    let user_style = load_style(style_path);
+    let syntax_css = theme.and_then(|t| css_for_theme_with_class_style(t, ClassStyle::Spaced).ok());
+
+    match (syntax_css, user_style) {
+        (Some(css), Some(user)) => Some(format!("{}\n{}", css, user)),
+        (Some(css), None) => Some(css),
+        (None, user) => user,
+    }
+}
+
+/// The bookkeeping a single page contributes to the site-wide aggregates (blog index,
+/// search index, and link checker) built after `build_one` writes its HTML.
+struct PageBuildInfo {
+    relative_path: PathBuf,
+    post: Option<PostSummary>,
+    search_id: String,
+    search_entry: Value,
+    internal_links: Vec<String>,
+    /// Tags from the page's front matter, and the listing entry to file under each one.
+    tags: Vec<String>,
+    tagged_page: Option<TaggedPage>,
+}
+
+/// Renders a single markdown file to its output HTML file. Used both by the full-site
+/// `WalkDir` loop and by dev-server mode to re-render one changed file in isolation.
+///
+/// # Arguments
+/// * `path` - Path to the source markdown file.
+/// * `content_path` - Path to the content directory for resolving relative paths.
+/// * `output_path` - Path to the site's output directory.
+/// * `style` - Optional stylesheet content.
+/// * `header` - Optional rendered header content.
+/// * `footer` - Optional rendered footer content.
+/// * `toc_levels` - Maximum heading depth to include in the table of contents.
+/// * `syntax_set` - The loaded syntect syntax definitions used to highlight fenced code blocks.
+/// * `theme` - The loaded syntax theme, if any; `None` disables highlighting.
+///
+/// # Returns
+/// * A `Result<PageBuildInfo>` describing what the page contributes to the site-wide aggregates.
+fn build_one(
+    path: &Path,
+    content_path: &Path,
+    output_path: &Path,
+    style: Option<&str>,
+    header: Option<&HTMLContent>,
+    footer: Option<&HTMLContent>,
+    toc_levels: usize,
+    syntax_set: &SyntaxSet,
+    theme: Option<&Theme>,
+) -> Result<PageBuildInfo> {
+    let html_content = load_html_from_md_file(path, content_path, toc_levels, syntax_set, theme)?;
+
+    let relative_path = path.strip_prefix(content_path)?.with_extension("html");
+    let file_output_path = output_path.join(&relative_path);
+
+    let post = collect_post_summary(path, content_path, &html_content, &relative_path);
+    let internal_links = html_content.internal_links.clone();
+
+    let url = format!("/{}", relative_path.to_string_lossy());
+    let title = html_content
+        .front_matter
+        .as_ref()
+        .and_then(|fm| fm.title.clone())
+        .unwrap_or_default();
+    let tags = html_content
+        .front_matter
+        .as_ref()
+        .map(|fm| fm.tags.clone())
+        .unwrap_or_default();
+    let tagged_page = if tags.is_empty() {
+        None
+    } else {
+        Some(TaggedPage {
+            title: title.clone(),
+            url: url.clone(),
+        })
+    };
+
+    let search_id = relative_path.with_extension("").to_string_lossy().to_string();
+    let headings_json: Vec<Value> = html_content
+        .headings
+        .iter()
+        .map(|h| serde_json::json!({ "text": h.text, "anchor": h.anchor }))
+        .collect();
+    let search_entry = serde_json::json!({
+        "url": url,
+        "title": title,
+        "body": html_content.plain_text.as_str(),
+        "headings": headings_json,
+    });
+
+    let final_html = render_template(
+        style,
+        header.map(|content| content.html.as_str()),
+        footer.map(|content| content.html.as_str()),
+        html_content,
+        None,
+    )
+    .with_context(|| "Failed to render template.")?;
+
+    if let Some(parent) = file_output_path.parent() {
+        fs::create_dir_all(parent)
+            .with_context(|| format!("Failed to create content directory: {:?}", parent))?;
+    }
+    fs::write(&file_output_path, final_html)
+        .with_context(|| format!("Failed to write generated HTML to file: {:?}", file_output_path))?;
+
+    Ok(PageBuildInfo {
+        relative_path,
+        post,
+        search_id,
+        search_entry,
+        internal_links,
+        tags,
+        tagged_page,
+    })
+}
+
+/// Validates every recorded internal link against the set of files actually emitted into
+/// `output_path`, reporting dangling links and failing the build unless `allow_broken_links` is set.
+///
+/// # Arguments
+/// * `link_checks` - Pairs of (source page relative to the site root, internal link destination).
+/// * `output_path` - The site's output directory, already fully populated.
+/// * `allow_broken_links` - When `true`, dangling links are reported but the build still succeeds.
+fn check_internal_links(link_checks: &[(PathBuf, String)], output_path: &Path, allow_broken_links: bool) {
+    let emitted: std::collections::HashSet<PathBuf> = WalkDir::new(output_path)
+        .into_iter()
+        .filter_map(|e| e.ok())
+        .filter(|e| e.file_type().is_file())
+        .filter_map(|e| e.path().strip_prefix(output_path).ok().map(Path::to_path_buf))
+        .collect();
+
+    let mut broken = Vec::new();
+    for (source, target) in link_checks {
+        let source_dir = source.parent().unwrap_or_else(|| Path::new(""));
+        let resolved = resolve_internal_link(target, source_dir);
+        if !emitted.contains(&resolved) {
+            broken.push((source, target));
+        }
+    }
+
+    if broken.is_empty() {
+        return;
+    }
+
+    for (source, target) in &broken {
+        eprintln!(
+            "Broken link: {:?} links to {:?}, which was never emitted.",
+            source, target
+        );
+    }
+
+    if !allow_broken_links {
+        eprintln!(
+            "Found {} broken internal link(s). Pass --allow-broken-links to build anyway.",
+            broken.len()
+        );
+        std::process::exit(1);
+    }
+}
+
+/// Decides whether a rewritten link destination points at something `generate_site` actually
+/// emits, as opposed to an external URL, a mail/phone link, a protocol-relative host reference,
+/// an anchor-only fragment, or some other local path (e.g. `data.csv`, `/about`) that isn't a
+/// rendered page or a copied static asset.
+///
+/// # Arguments
+/// * `dest` - The rewritten link destination, as produced by the `.md`-to-`.html` rewrite.
+///
+/// # Returns
+/// * `true` if the destination is a rewritten `.html` page, a directory link, or a path under
+///   `static/`, i.e. one of the only things the site writes to `output_path`.
+fn is_internal_link(dest: &str) -> bool {
+    if dest.is_empty() || dest.starts_with('#') || dest.starts_with("//") {
+        return false;
+    }
+    let path_part = dest.split('#').next().unwrap_or("");
+    let has_scheme = path_part
+        .split_once(':')
+        .map_or(false, |(scheme, _)| !scheme.contains('/'));
+    if has_scheme {
+        return false;
+    }
+
+    path_part.ends_with(".html")
+        || path_part.ends_with('/')
+        || path_part.starts_with("/static/")
+        || path_part.starts_with("static/")
+}
+
+/// Resolves an internal link destination to a path relative to the site root.
+///
+/// Root-relative destinations (starting with `/`) resolve against the site root; anything
+/// else resolves against the linking page's directory. Destinations ending in `/` (directory
+/// links produced by index-page rewriting) and any `#fragment` suffix are handled as well.
+///
+/// # Arguments
+/// * `target` - The rewritten link destination, as recorded from `markdown_to_html`.
+/// * `source_dir` - The linking page's directory, relative to the site root.
+///
+/// # Returns
+/// * The resolved path, relative to the site root, that should exist among the emitted files.
+fn resolve_internal_link(target: &str, source_dir: &Path) -> PathBuf {
+    let path_part = target.split('#').next().unwrap_or("");
+
+    let mut resolved = if let Some(root_relative) = path_part.strip_prefix('/') {
+        PathBuf::from(root_relative)
+    } else {
+        source_dir.join(path_part)
+    };
+
+    if path_part.is_empty() || path_part.ends_with('/') {
+        resolved = resolved.join("index.html");
+    }
+
+    normalize_path(&resolved)
+}
+
+/// Lexically collapses `.` and `..` path components without touching the filesystem.
+///
+/// # Arguments
+/// * `path` - The path to normalize.
+///
+/// # Returns
+/// * The normalized path.
+fn normalize_path(path: &Path) -> PathBuf {
+    let mut result = PathBuf::new();
+    for component in path.components() {
+        match component {
+            std::path::Component::ParentDir => {
+                result.pop();
+            }
+            std::path::Component::CurDir => {}
+            other => result.push(other.as_os_str()),
         }
     }
+    result
+}
+
+/// Builds a listing entry for a page if it opted into the blog post index.
+///
+/// A page is included when its front matter carries a parseable `date` and either sets
+/// `list: true` or lives under a directory named `posts`.
+///
+/// # Arguments
+/// * `path` - Path to the source markdown file.
+/// * `content_path` - Path to the content directory, used to detect the `posts` directory convention.
+/// * `html_content` - The rendered content, whose front matter is inspected.
+/// * `relative_output_path` - The page's output path relative to the site root, used to build its link.
+///
+/// # Returns
+/// * `Some(PostSummary)` if the page should appear in the listing, `None` otherwise.
+fn collect_post_summary(
+    path: &Path,
+    content_path: &Path,
+    html_content: &HTMLContent,
+    relative_output_path: &Path,
+) -> Option<PostSummary> {
+    let front_matter = html_content.front_matter.as_ref()?;
+    let date = front_matter.date?;
+
+    let in_posts_dir = path
+        .strip_prefix(content_path)
+        .ok()
+        .map(|relative| relative.components().any(|c| c.as_os_str() == "posts"))
+        .unwrap_or(false);
+
+    if !front_matter.list && !in_posts_dir {
+        return None;
+    }
+
+    Some(PostSummary {
+        title: front_matter.title.clone().unwrap_or_default(),
+        date,
+        url: format!("/{}", relative_output_path.to_string_lossy()),
+    })
 }
 
 /// Retrieves the absolute path for the content directory, handling expansion of any user variables.
@@ -177,9 +801,15 @@ fn load_style(style_path: impl AsRef<str>) -> Option<String> {
 ///
 /// # Returns
 /// * An `Option<HTMLContent>` containing the processed header HTML content, or `None` if an error occurs.
-fn load_header(header_path: impl AsRef<str>, content_path: &Path) -> Option<HTMLContent> {
+fn load_header(
+    header_path: impl AsRef<str>,
+    content_path: &Path,
+    toc_levels: usize,
+    syntax_set: &SyntaxSet,
+    theme: Option<&Theme>,
+) -> Option<HTMLContent> {
     let header_path = get_absolute_path(header_path).ok()?;
-    load_html_from_md_file(&header_path, content_path).ok()
+    load_html_from_md_file(&header_path, content_path, toc_levels, syntax_set, theme).ok()
 }
 
 /// Loads and processes the footer markdown file into HTML content.
@@ -190,9 +820,15 @@ fn load_header(header_path: impl AsRef<str>, content_path: &Path) -> Option<HTML
 ///
 /// # Returns
 /// * An `Option<HTMLContent>` containing the processed footer HTML content, or `None` if an error occurs.
-fn load_footer(footer_path: impl AsRef<str>, content_path: &Path) -> Option<HTMLContent> {
+fn load_footer(
+    footer_path: impl AsRef<str>,
+    content_path: &Path,
+    toc_levels: usize,
+    syntax_set: &SyntaxSet,
+    theme: Option<&Theme>,
+) -> Option<HTMLContent> {
     let footer_path = get_absolute_path(footer_path).ok()?;
-    load_html_from_md_file(&footer_path, content_path).ok()
+    load_html_from_md_file(&footer_path, content_path, toc_levels, syntax_set, theme).ok()
 }
 
 /// Converts a given markdown file's contents to HTML, incorporating the site's layout.
@@ -200,20 +836,33 @@ fn load_footer(footer_path: impl AsRef<str>, content_path: &Path) -> Option<HTML
 /// # Arguments
 /// * `path` - Path to the markdown file.
 /// * `content_path` - Path to the content directory for resolving relative paths.
+/// * `toc_levels` - Maximum heading depth to include in the table of contents.
+/// * `syntax_set` - The loaded syntect syntax definitions used to highlight fenced code blocks.
+/// * `theme` - The loaded syntax theme, if any; `None` disables highlighting.
 ///
 /// # Returns
 /// * A `Result<HTMLContent>` containing the HTML content or an error if conversion fails.
-fn load_html_from_md_file(path: &Path, content_path: &Path) -> Result<HTMLContent> {
+fn load_html_from_md_file(
+    path: &Path,
+    content_path: &Path,
+    toc_levels: usize,
+    syntax_set: &SyntaxSet,
+    theme: Option<&Theme>,
+) -> Result<HTMLContent> {
     fs::read_to_string(&path)
         .with_context(|| format!("Failed to read from markdown file: {:?}", path))
         .and_then(|file_content| process_markdown(&file_content))
         .with_context(|| "Failed to process markdown file.")
         .and_then(|markdown_content| {
-            let html = markdown_to_html(&markdown_content.markdown, content_path)
+            let html = markdown_to_html(&markdown_content.markdown, content_path, toc_levels, syntax_set, theme)
                 .with_context(|| "Failed to convert markdown to HTML.")?;
             Ok(HTMLContent {
                 front_matter: markdown_content.front_matter,
-                html,
+                html: html.html,
+                plain_text: html.plain_text,
+                headings: html.headings,
+                toc: html.toc,
+                internal_links: html.internal_links,
             })
         })
 }
@@ -233,19 +882,33 @@ fn render_template(
     header: Option<&str>,
     footer: Option<&str>,
     content: HTMLContent,
+    extra: Option<Value>,
 ) -> Result<String> {
     let mut handlebars = Handlebars::new();
     handlebars.register_template_string("template", include_str!("./template.html"))?;
 
-    let data = serde_json::json!({
+    let toc_json: Vec<Value> = content
+        .toc
+        .iter()
+        .map(|entry| serde_json::json!({ "level": entry.level, "text": entry.text, "anchor": entry.anchor }))
+        .collect();
+
+    let mut data = serde_json::json!({
         "title": content.front_matter.as_ref().map_or("", |fm| fm.title.as_deref().unwrap_or("")),
         "description": content.front_matter.as_ref().map_or("", |fm| fm.description.as_deref().unwrap_or("")) ,
         "style": style.as_deref().unwrap_or(""),
         "header": header.as_deref().unwrap_or(""),
         "footer": footer.as_deref().unwrap_or(""),
+        "toc": toc_json,
         "content": content.html
     });
 
+    if let Some(extra) = extra {
+        if let (Value::Object(data_map), Value::Object(extra_map)) = (&mut data, extra) {
+            data_map.extend(extra_map);
+        }
+    }
+
     Ok(handlebars.render("template", &data)?)
 }
 
@@ -275,6 +938,26 @@ fn expand_path(path: impl AsRef<str>) -> String {
 struct FrontMatter {
     title: Option<String>,
     description: Option<String>,
+    /// The page's publication date, parsed from a `YYYY-MM-DD` front matter value.
+    date: Option<NaiveDate>,
+    /// Whether the page opted in to the blog post index via `list: true`.
+    list: bool,
+    /// Tags listed under the page's front matter `tags` and/or `categories` key, used to
+    /// generate `/tags/<slug>.html` pages.
+    tags: Vec<String>,
+}
+
+/// A single entry in the generated blog post index.
+struct PostSummary {
+    title: String,
+    date: NaiveDate,
+    url: String,
+}
+
+/// A page entry listed on a tag's generated page.
+struct TaggedPage {
+    title: String,
+    url: String,
 }
 
 /// Data structure for holding markdown content, potentially including extracted front matter.
@@ -287,6 +970,14 @@ struct MarkdownContent {
 struct HTMLContent {
     front_matter: Option<FrontMatter>,
     html: String,
+    /// Plain-text body (markup stripped) used to build the client-side search index.
+    plain_text: String,
+    /// Headings encountered in the document, used to deep-link search results.
+    headings: Vec<HeadingAnchor>,
+    /// Table of contents outline, empty when the page has fewer than two qualifying headings.
+    toc: Vec<TocEntry>,
+    /// Rewritten internal link destinations found in the document, for the broken-link check.
+    internal_links: Vec<String>,
 }
 
 /// Processes markdown content, extracting front matter if present, and converts it to structured content.
@@ -311,13 +1002,36 @@ fn process_markdown(content: &str) -> Result<MarkdownContent> {
                 .with_context(|| "Failed to parse YAML front matter.")?;
 
             // Extract title from front matter, default to empty if not present
-            let title = front_matter.get("title").map(Value::to_string);
-            let meta_description = front_matter.get("meta_description").map(Value::to_string);
+            let title = front_matter.get("title").and_then(Value::as_str).map(str::to_string);
+            let meta_description = front_matter
+                .get("meta_description")
+                .and_then(Value::as_str)
+                .map(str::to_string);
+            let date = front_matter
+                .get("date")
+                .and_then(Value::as_str)
+                .and_then(|s| parse_front_matter_date(s));
+            let list = front_matter
+                .get("list")
+                .and_then(Value::as_bool)
+                .unwrap_or(false);
+            // `categories` is treated as an alias for `tags`: both feed the same taxonomy, so a
+            // page can use whichever term its author prefers (or both) and still land on one
+            // set of generated tag pages.
+            let mut tags = parse_string_list(&front_matter, "tags");
+            for category in parse_string_list(&front_matter, "categories") {
+                if !tags.iter().any(|tag| tag.eq_ignore_ascii_case(&category)) {
+                    tags.push(category);
+                }
+            }
 
             Ok(MarkdownContent {
                 front_matter: Some(FrontMatter {
                     title,
                     description: meta_description,
+                    date,
+                    list,
+                    tags,
                 }),
                 markdown: rest_content.trim_start().to_string(),
             })
@@ -335,15 +1049,87 @@ fn process_markdown(content: &str) -> Result<MarkdownContent> {
     }
 }
 
+/// Parses a front matter `date` value, warning and discarding it if it isn't `YYYY-MM-DD`.
+///
+/// # Arguments
+/// * `value` - The raw date string from front matter.
+///
+/// # Returns
+/// * `Some(NaiveDate)` if the value parses, `None` otherwise.
+fn parse_front_matter_date(value: &str) -> Option<NaiveDate> {
+    match NaiveDate::parse_from_str(value, "%Y-%m-%d") {
+        Ok(date) => Some(date),
+        Err(e) => {
+            eprintln!("Warning: ignoring invalid front matter date {:?}: {}", value, e);
+            None
+        }
+    }
+}
+
+/// Reads a list-valued front matter key (e.g. `tags`, `categories`) into a plain string list,
+/// dropping any non-string entries.
+///
+/// # Arguments
+/// * `front_matter` - The parsed YAML front matter.
+/// * `key` - The front matter key to read.
+///
+/// # Returns
+/// * The key's string entries, or an empty `Vec` if the key is absent or not a list.
+fn parse_string_list(front_matter: &Value, key: &str) -> Vec<String> {
+    front_matter
+        .get(key)
+        .and_then(Value::as_array)
+        .map(|values| {
+            values
+                .iter()
+                .filter_map(Value::as_str)
+                .map(str::to_string)
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// A heading encountered while converting markdown to HTML, captured for search deep-linking.
+struct HeadingAnchor {
+    text: String,
+    anchor: String,
+}
+
+/// An entry in a page's table of contents outline.
+struct TocEntry {
+    level: usize,
+    text: String,
+    anchor: String,
+}
+
+/// The result of converting a markdown document to HTML: the markup itself plus the
+/// plain-text body, heading anchors, and table of contents extracted along the way.
+struct MarkdownHtml {
+    html: String,
+    plain_text: String,
+    headings: Vec<HeadingAnchor>,
+    toc: Vec<TocEntry>,
+    internal_links: Vec<String>,
+}
+
 /// Converts markdown text to HTML format using a specified content directory to resolve paths.
 ///
 /// # Arguments
 /// * markdown_input - The markdown text to convert.
 /// * content_dir - The content directory used for path resolution in the markdown.
+/// * toc_levels - Maximum heading depth (1-based) to include in the table of contents.
+/// * syntax_set - The loaded syntect syntax definitions used to highlight fenced code blocks.
+/// * theme - The loaded syntax theme, if any; `None` disables highlighting.
 ///
 /// # Returns
-/// * A Result<String> containing the converted HTML text or an error if the conversion fails.
-fn markdown_to_html(markdown_input: &str, content_dir: &Path) -> Result<String> {
+/// * A Result<MarkdownHtml> containing the converted HTML, plain text, headings, and TOC, or an error if the conversion fails.
+fn markdown_to_html(
+    markdown_input: &str,
+    content_dir: &Path,
+    toc_levels: usize,
+    syntax_set: &SyntaxSet,
+    theme: Option<&Theme>,
+) -> Result<MarkdownHtml> {
     let parser = pulldown_cmark::Parser::new_ext(markdown_input, Options::all());
     let mut events: Vec<Event> = Vec::new();
 
@@ -356,6 +1142,16 @@ fn markdown_to_html(markdown_input: &str, content_dir: &Path) -> Result<String>
             .unwrap_or("")
     );
 
+    let mut plain_text = String::new();
+    let mut headings: Vec<HeadingAnchor> = Vec::new();
+    let mut toc: Vec<TocEntry> = Vec::new();
+    let mut internal_links: Vec<String> = Vec::new();
+    let mut seen_slugs: std::collections::HashMap<String, usize> = std::collections::HashMap::new();
+    // (index of the heading's Start event in `events`, accumulated text, heading level)
+    let mut current_heading: Option<(usize, String, usize)> = None;
+    // (fence language, accumulated raw code) while buffering a fenced code block we intend to highlight
+    let mut current_code_block: Option<(String, String)> = None;
+
     for event in parser {
         match event {
             Event::Start(Tag::Link {
@@ -381,6 +1177,10 @@ fn markdown_to_html(markdown_input: &str, content_dir: &Path) -> Result<String>
                     dest_url.to_string()
                 };
 
+                if is_internal_link(&new_dest) {
+                    internal_links.push(new_dest.clone());
+                }
+
                 // Push the modified or original link event
                 events.push(Event::Start(Tag::Link {
                     link_type,
@@ -389,11 +1189,175 @@ fn markdown_to_html(markdown_input: &str, content_dir: &Path) -> Result<String>
                     id,
                 }));
             }
+            Event::Start(Tag::Heading { level, .. }) => {
+                current_heading = Some((events.len(), String::new(), level as usize));
+                events.push(event);
+            }
+            Event::Start(Tag::CodeBlock(pulldown_cmark::CodeBlockKind::Fenced(lang))) => {
+                let highlightable = theme.is_some()
+                    && !lang.is_empty()
+                    && syntax_set.find_syntax_by_token(&lang).is_some();
+                if highlightable {
+                    current_code_block = Some((lang.to_string(), String::new()));
+                } else {
+                    events.push(Event::Start(Tag::CodeBlock(
+                        pulldown_cmark::CodeBlockKind::Fenced(lang),
+                    )));
+                }
+            }
+            Event::End(TagEnd::CodeBlock) => {
+                match current_code_block.take() {
+                    Some((lang, code)) => {
+                        let html = highlight_code_block(&lang, &code, syntax_set, theme);
+                        events.push(Event::Html(CowStr::Boxed(html.into_boxed_str())));
+                    }
+                    None => events.push(event),
+                }
+            }
+            Event::End(TagEnd::Heading(_)) => {
+                if let Some((idx, text, level)) = current_heading.take() {
+                    let anchor = unique_slug(&slugify(&text), &mut seen_slugs);
+
+                    if let Event::Start(Tag::Heading {
+                        level,
+                        classes,
+                        attrs,
+                        ..
+                    }) = events[idx].clone()
+                    {
+                        events[idx] = Event::Start(Tag::Heading {
+                            level,
+                            id: Some(CowStr::Boxed(anchor.clone().into_boxed_str())),
+                            classes,
+                            attrs,
+                        });
+                    }
+
+                    if level <= toc_levels {
+                        toc.push(TocEntry {
+                            level,
+                            text: text.clone(),
+                            anchor: anchor.clone(),
+                        });
+                    }
+                    headings.push(HeadingAnchor { text, anchor });
+                }
+                events.push(event);
+            }
+            Event::Text(text) => {
+                if let Some(heading) = current_heading.as_mut() {
+                    heading.1.push_str(&text);
+                }
+                plain_text.push_str(&text);
+                plain_text.push(' ');
+                if let Some((_, code)) = current_code_block.as_mut() {
+                    code.push_str(&text);
+                } else {
+                    events.push(Event::Text(text));
+                }
+            }
+            Event::Code(text) => {
+                if let Some(heading) = current_heading.as_mut() {
+                    heading.1.push_str(&text);
+                }
+                plain_text.push_str(&text);
+                plain_text.push(' ');
+                events.push(Event::Code(text));
+            }
             _ => events.push(event),
         }
     }
 
+    if toc.len() < 2 {
+        toc.clear();
+    }
+
     let mut html_output = String::new();
     push_html(&mut html_output, events.into_iter());
-    Ok(html_output)
+    Ok(MarkdownHtml {
+        html: html_output,
+        plain_text: plain_text.trim().to_string(),
+        headings,
+        toc,
+        internal_links,
+    })
+}
+
+/// Highlights a fenced code block's contents into classed `<span>` markup.
+///
+/// The caller only buffers a code block for this when a syntax theme is loaded and a syntax
+/// matching `lang` is registered, so both are assumed to succeed here.
+///
+/// # Arguments
+/// * `lang` - The fence's language token, e.g. `rust`.
+/// * `code` - The code block's raw, unescaped text.
+/// * `syntax_set` - The loaded syntect syntax definitions.
+/// * `theme` - The loaded syntax theme.
+///
+/// # Returns
+/// * The rendered `<pre class="language-...">` markup for the block.
+fn highlight_code_block(lang: &str, code: &str, syntax_set: &SyntaxSet, theme: Option<&Theme>) -> String {
+    let syntax = theme
+        .and_then(|_| syntax_set.find_syntax_by_token(lang))
+        .unwrap_or_else(|| syntax_set.find_syntax_plain_text());
+
+    let mut generator = ClassedHTMLGenerator::new_with_class_style(syntax, syntax_set, ClassStyle::Spaced);
+    for line in LinesWithEndings::from(code) {
+        let _ = generator.parse_html_for_line_which_includes_newline(line);
+    }
+
+    format!(
+        "<pre class=\"language-{}\"><code>{}</code></pre>\n",
+        lang,
+        generator.finalize()
+    )
+}
+
+/// Makes a slug unique within a document by appending a numeric suffix to repeats.
+///
+/// # Arguments
+/// * `base` - The candidate slug, as produced by `slugify`.
+/// * `seen` - Counts of slugs already assigned in this document.
+///
+/// # Returns
+/// * A slug guaranteed not to collide with any previously returned slug from the same `seen` map.
+fn unique_slug(base: &str, seen: &mut std::collections::HashMap<String, usize>) -> String {
+    let base = if base.is_empty() { "section" } else { base };
+    let mut count = seen.get(base).copied().unwrap_or(0);
+    loop {
+        let candidate = if count == 0 {
+            base.to_string()
+        } else {
+            format!("{}-{}", base, count)
+        };
+        count += 1;
+        if !seen.contains_key(&candidate) {
+            seen.insert(base.to_string(), count);
+            seen.entry(candidate.clone()).or_insert(1);
+            return candidate;
+        }
+    }
+}
+
+/// Slugifies text into a URL-safe anchor id: lowercased, non-alphanumeric runs become a
+/// single hyphen, and leading/trailing hyphens are trimmed.
+///
+/// # Arguments
+/// * `text` - The heading text to slugify.
+///
+/// # Returns
+/// * The slugified anchor id.
+fn slugify(text: &str) -> String {
+    let mut slug = String::with_capacity(text.len());
+    let mut last_was_hyphen = false;
+    for c in text.trim().chars() {
+        if c.is_alphanumeric() {
+            slug.extend(c.to_lowercase());
+            last_was_hyphen = false;
+        } else if !last_was_hyphen {
+            slug.push('-');
+            last_was_hyphen = true;
+        }
+    }
+    slug.trim_matches('-').to_string()
 }